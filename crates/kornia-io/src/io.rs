@@ -0,0 +1,249 @@
+use std::path::Path;
+
+use kornia_image::{Image, ImageSize};
+
+use crate::jpeg::{self, JpegError};
+use crate::tiff::{self, TiffError};
+
+/// Error types for the unified `read_image` entry point.
+#[derive(thiserror::Error, Debug)]
+pub enum ReadImageError {
+    /// Error when reading the file from disk.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Error when the leading bytes do not match any known image format.
+    #[error("Unknown image format (signature: {0:02x?})")]
+    UnknownFormat(Vec<u8>),
+
+    /// Error when the leading bytes match a recognized image format that
+    /// this crate has no decoder for yet.
+    #[error("Recognized but unsupported image format: {0:?}")]
+    UnsupportedFormat(RecognizedFormat),
+
+    /// Error coming from the JPEG codec.
+    #[error(transparent)]
+    Jpeg(#[from] JpegError),
+
+    /// Error coming from the TIFF codec.
+    #[error(transparent)]
+    Tiff(#[from] TiffError),
+}
+
+/// An image format this crate can actually decode, detected from the
+/// leading bytes of a file rather than trusting its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// JPEG, signature `FF D8 FF`.
+    Jpeg,
+    /// TIFF, signature `49 49 2A 00` (little-endian) or `4D 4D 00 2A` (big-endian).
+    Tiff,
+}
+
+/// An image format whose signature is recognized, but for which this crate
+/// has no decoder (yet).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecognizedFormat {
+    /// PNG, signature `89 50 4E 47`.
+    Png,
+}
+
+/// Detects the image format from the magic bytes at the start of `data`.
+///
+/// # Arguments
+///
+/// * `data` - The raw (encoded) image bytes.
+///
+/// # Returns
+///
+/// The detected [`ImageFormat`], [`ReadImageError::UnsupportedFormat`] if the
+/// signature is recognized but not (yet) decodable (e.g. PNG), or
+/// [`ReadImageError::UnknownFormat`] if the signature is not recognized at all.
+pub fn detect_format(data: &[u8]) -> Result<ImageFormat, ReadImageError> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Ok(ImageFormat::Jpeg)
+    } else if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+        Ok(ImageFormat::Tiff)
+    } else if data.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
+        Err(ReadImageError::UnsupportedFormat(RecognizedFormat::Png))
+    } else {
+        Err(ReadImageError::UnknownFormat(
+            data.get(..4.min(data.len())).unwrap_or(data).to_vec(),
+        ))
+    }
+}
+
+/// A decoded image, with whichever channel count and bit depth its source
+/// format and color type produced.
+pub enum DecodedImage {
+    /// An 8-bit single-channel (grayscale) image.
+    Gray8(Image<u8, 1>),
+    /// An 8-bit three-channel (RGB) image.
+    Rgb8(Image<u8, 3>),
+    /// A 16-bit single-channel (grayscale) image.
+    Gray16(Image<u16, 1>),
+    /// A 16-bit three-channel (RGB) image.
+    Rgb16(Image<u16, 3>),
+}
+
+impl From<jpeg::DecodedImage> for DecodedImage {
+    fn from(image: jpeg::DecodedImage) -> Self {
+        match image {
+            jpeg::DecodedImage::Gray(image) => DecodedImage::Gray8(image),
+            jpeg::DecodedImage::Rgb(image) => DecodedImage::Rgb8(image),
+        }
+    }
+}
+
+impl From<tiff::DecodedImage> for DecodedImage {
+    fn from(image: tiff::DecodedImage) -> Self {
+        match image {
+            tiff::DecodedImage::Gray8(image) => DecodedImage::Gray8(image),
+            tiff::DecodedImage::Rgb8(image) => DecodedImage::Rgb8(image),
+            tiff::DecodedImage::Gray16(image) => DecodedImage::Gray16(image),
+            tiff::DecodedImage::Rgb16(image) => DecodedImage::Rgb16(image),
+        }
+    }
+}
+
+/// A handle to a JPEG image that has not been decoded yet.
+///
+/// Retains the compressed bytes and the `ImageSize` parsed from the JPEG
+/// header, so callers that only need the dimensions (or that hand the bytes
+/// straight to e.g. a GPU JPEG decoder) never pay for a pixel decode.
+pub struct DeferredJpeg {
+    data: Vec<u8>,
+    size: ImageSize,
+}
+
+impl DeferredJpeg {
+    /// The image dimensions, read from the JPEG header.
+    pub fn size(&self) -> ImageSize {
+        self.size
+    }
+
+    /// The compressed JPEG bytes backing this handle.
+    pub fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Decodes the retained bytes into pixels. The result is not cached:
+    /// every call runs a full decode.
+    pub fn decode(&self) -> Result<DecodedImage, JpegError> {
+        Ok(jpeg::ImageDecoder::new()?.decode_auto(&self.data)?.into())
+    }
+}
+
+/// Either an already-decoded image, or a deferred JPEG handle whose pixels
+/// have not been decoded yet.
+pub enum ReadImage {
+    /// An image that was decoded eagerly.
+    Decoded(DecodedImage),
+    /// A JPEG image whose pixel decode has been deferred until first access.
+    DeferredJpeg(DeferredJpeg),
+}
+
+/// Reads and decodes an image file, detecting its format from its magic bytes.
+///
+/// # Arguments
+///
+/// * `path` - The path to the image file.
+///
+/// # Returns
+///
+/// The decoded image.
+pub fn read_image(path: impl AsRef<Path>) -> Result<DecodedImage, ReadImageError> {
+    read_image_bytes(&std::fs::read(path)?)
+}
+
+/// Decodes an in-memory image, detecting its format from its magic bytes.
+///
+/// # Arguments
+///
+/// * `data` - The raw (encoded) image bytes.
+///
+/// # Returns
+///
+/// The decoded image.
+pub fn read_image_bytes(data: &[u8]) -> Result<DecodedImage, ReadImageError> {
+    Ok(match detect_format(data)? {
+        ImageFormat::Jpeg => jpeg::ImageDecoder::new()?.decode_auto(data)?.into(),
+        ImageFormat::Tiff => tiff::ImageDecoder::new().decode_auto(data)?.into(),
+    })
+}
+
+/// Reads an image file, detecting its format from its magic bytes, deferring
+/// the pixel decode for JPEG inputs until [`DeferredJpeg::decode`] is called.
+///
+/// # Arguments
+///
+/// * `path` - The path to the image file.
+///
+/// # Returns
+///
+/// Either a decoded image, or (for JPEG input) a [`DeferredJpeg`] handle.
+pub fn read_image_deferred(path: impl AsRef<Path>) -> Result<ReadImage, ReadImageError> {
+    read_image_bytes_deferred(std::fs::read(path)?)
+}
+
+/// Same as [`read_image_deferred`], but from in-memory bytes.
+///
+/// # Arguments
+///
+/// * `data` - The raw (encoded) image bytes.
+///
+/// # Returns
+///
+/// Either a decoded image, or (for JPEG input) a [`DeferredJpeg`] handle.
+pub fn read_image_bytes_deferred(data: Vec<u8>) -> Result<ReadImage, ReadImageError> {
+    match detect_format(&data)? {
+        ImageFormat::Jpeg => {
+            let size = jpeg::ImageDecoder::new()?.read_header(&data)?;
+            Ok(ReadImage::DeferredJpeg(DeferredJpeg { data, size }))
+        }
+        _ => Ok(ReadImage::Decoded(read_image_bytes(&data)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_jpeg_by_magic_bytes() -> Result<(), ReadImageError> {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+        assert_eq!(detect_format(&jpeg_data)?, ImageFormat::Jpeg);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_signature() {
+        let err = detect_format(&[0, 1, 2, 3]).unwrap_err();
+        assert!(matches!(err, ReadImageError::UnknownFormat(_)));
+    }
+
+    #[test]
+    fn recognizes_png_as_unsupported_rather_than_unknown() {
+        let png_signature = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        let err = detect_format(&png_signature).unwrap_err();
+        assert!(matches!(
+            err,
+            ReadImageError::UnsupportedFormat(RecognizedFormat::Png)
+        ));
+    }
+
+    #[test]
+    fn read_image_deferred_jpeg_decodes_lazily() -> Result<(), ReadImageError> {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+        let handle = read_image_bytes_deferred(jpeg_data)?;
+        let ReadImage::DeferredJpeg(deferred) = handle else {
+            panic!("expected a deferred JPEG handle");
+        };
+        assert_eq!(deferred.size().width, 258);
+        assert_eq!(deferred.size().height, 195);
+
+        let decoded = deferred.decode()?;
+        assert!(matches!(decoded, DecodedImage::Rgb8(_)));
+        Ok(())
+    }
+}
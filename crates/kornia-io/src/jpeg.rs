@@ -17,6 +17,52 @@ pub enum JpegError {
     /// Error to create the image.
     #[error("Failed to create image")]
     ImageCreationError(#[from] ImageError),
+
+    /// Error when the requested channel count has no corresponding JPEG pixel format.
+    #[error("Unsupported number of channels: {0} (expected 1, 3 or 4)")]
+    UnsupportedChannelCount(usize),
+
+    /// Error when the JPEG's colorspace has no automatic conversion to RGB.
+    #[error("Unsupported colorspace for automatic decoding: {0:?}")]
+    UnsupportedColorspace(turbojpeg::Colorspace),
+
+    /// Error when a requested decode region falls outside the image bounds.
+    #[error(
+        "Region (x={x}, y={y}, w={w}, h={h}) is out of bounds for a {image_width}x{image_height} image"
+    )]
+    RegionOutOfBounds {
+        /// Left edge of the requested region.
+        x: usize,
+        /// Top edge of the requested region.
+        y: usize,
+        /// Width of the requested region.
+        w: usize,
+        /// Height of the requested region.
+        h: usize,
+        /// Width of the source image.
+        image_width: usize,
+        /// Height of the source image.
+        image_height: usize,
+    },
+}
+
+impl From<crate::optimize::OptimizeError> for JpegError {
+    fn from(err: crate::optimize::OptimizeError) -> Self {
+        match err {
+            crate::optimize::OptimizeError::TurboJpegError(e) => JpegError::TurboJpegError(e),
+        }
+    }
+}
+
+/// Maps an image channel count to the turbojpeg pixel format used to
+/// interpret its pixel data (1 = grayscale, 3 = RGB, 4 = RGBA).
+fn pixel_format_for<const C: usize>() -> Result<turbojpeg::PixelFormat, JpegError> {
+    match C {
+        1 => Ok(turbojpeg::PixelFormat::GRAY),
+        3 => Ok(turbojpeg::PixelFormat::RGB),
+        4 => Ok(turbojpeg::PixelFormat::RGBA),
+        _ => Err(JpegError::UnsupportedChannelCount(C)),
+    }
 }
 
 /// A JPEG decoder using the turbojpeg library.
@@ -31,6 +77,38 @@ pub struct ImageEncoder {
     pub compressor: Arc<Mutex<turbojpeg::Compressor>>,
 }
 
+/// A decoded JPEG image whose channel count matches the source's own pixel
+/// format, as picked by [`ImageDecoder::decode_auto`].
+///
+/// There is no `Rgba` variant: libjpeg-turbo never reports a colorspace that
+/// [`ImageDecoder::decode_auto`] maps to 4 channels (CMYK/YCCK are rejected
+/// outright, see its doc comment), so such a variant could never be
+/// constructed through the only public producer of this type.
+pub enum DecodedImage {
+    /// A single-channel (grayscale) image.
+    Gray(Image<u8, 1>),
+    /// A three-channel (RGB) image.
+    Rgb(Image<u8, 3>),
+}
+
+impl DecodedImage {
+    /// The number of channels of the underlying image.
+    pub fn num_channels(&self) -> usize {
+        match self {
+            DecodedImage::Gray(_) => 1,
+            DecodedImage::Rgb(_) => 3,
+        }
+    }
+
+    /// The size of the underlying image.
+    pub fn size(&self) -> ImageSize {
+        match self {
+            DecodedImage::Gray(image) => image.size(),
+            DecodedImage::Rgb(image) => image.size(),
+        }
+    }
+}
+
 impl Default for ImageDecoder {
     fn default() -> Self {
         match Self::new() {
@@ -69,6 +147,9 @@ impl ImageEncoder {
 
     /// Encodes the given data into a JPEG image.
     ///
+    /// The pixel format is derived from the image's channel count: 1 channel
+    /// is encoded as grayscale, 3 as RGB and 4 as RGBA.
+    ///
     /// # Arguments
     ///
     /// * `image` - The image to encode.
@@ -76,7 +157,9 @@ impl ImageEncoder {
     /// # Returns
     ///
     /// The encoded data as `Vec<u8>`.
-    pub fn encode(&mut self, image: &Image<u8, 3>) -> Result<Vec<u8>, JpegError> {
+    pub fn encode<const C: usize>(&mut self, image: &Image<u8, C>) -> Result<Vec<u8>, JpegError> {
+        let format = pixel_format_for::<C>()?;
+
         // get the image data
         let image_data = image.as_slice();
 
@@ -84,9 +167,9 @@ impl ImageEncoder {
         let buf = turbojpeg::Image {
             pixels: image_data,
             width: image.width(),
-            pitch: 3 * image.width(),
+            pitch: C * image.width(),
             height: image.height(),
-            format: turbojpeg::PixelFormat::RGB,
+            format,
         };
 
         // encode the image
@@ -101,6 +184,40 @@ impl ImageEncoder {
     pub fn set_quality(&mut self, quality: i32) -> Result<(), JpegError> {
         Ok(self.compressor.lock().unwrap().set_quality(quality)?)
     }
+
+    /// Sets the chroma subsampling scheme used when encoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `subsamp` - The subsampling scheme, e.g. 4:4:4, 4:2:2, 4:2:0 or grayscale.
+    pub fn set_subsamp(&mut self, subsamp: turbojpeg::Subsamp) -> Result<(), JpegError> {
+        Ok(self.compressor.lock().unwrap().set_subsamp(subsamp)?)
+    }
+
+    /// Enables or disables progressive (multi-scan) JPEG encoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `progressive` - Whether to emit a progressive JPEG instead of a baseline one.
+    pub fn set_progressive(&mut self, progressive: bool) -> Result<(), JpegError> {
+        Ok(self
+            .compressor
+            .lock()
+            .unwrap()
+            .set_progressive(progressive)?)
+    }
+
+    /// Enables or disables optimized (non-default) Huffman tables.
+    ///
+    /// Trades extra encoding time for a smaller output file, without any loss
+    /// in image quality.
+    ///
+    /// # Arguments
+    ///
+    /// * `optimize` - Whether to compute optimal Huffman tables for the image.
+    pub fn set_optimize(&mut self, optimize: bool) -> Result<(), JpegError> {
+        Ok(self.compressor.lock().unwrap().set_optimize(optimize)?)
+    }
 }
 
 /// Implementation of the ImageDecoder struct.
@@ -140,7 +257,11 @@ impl ImageDecoder {
         })
     }
 
-    /// Decodes the given JPEG data.
+    /// Decodes the given JPEG data into an image with `C` channels.
+    ///
+    /// The pixel format requested from turbojpeg follows `C`: 1 channel is
+    /// decoded as grayscale, 3 as RGB and 4 as RGBA (libjpeg-turbo fills the
+    /// alpha channel with `0xff`, since JPEG itself carries no alpha).
     ///
     /// # Arguments
     ///
@@ -148,21 +269,23 @@ impl ImageDecoder {
     ///
     /// # Returns
     ///
-    /// The decoded data as Tensor.
-    pub fn decode(&mut self, jpeg_data: &[u8]) -> Result<Image<u8, 3>, JpegError> {
+    /// The decoded data as an `Image`.
+    pub fn decode<const C: usize>(&mut self, jpeg_data: &[u8]) -> Result<Image<u8, C>, JpegError> {
+        let format = pixel_format_for::<C>()?;
+
         // get the image size to allocate th data storage
         let image_size = self.read_header(jpeg_data)?;
 
         // prepare a storage for the raw pixel data
-        let mut pixels = vec![0u8; image_size.height * image_size.width * 3];
+        let mut pixels = vec![0u8; image_size.height * image_size.width * C];
 
         // allocate image container
         let buf = turbojpeg::Image {
             pixels: pixels.as_mut_slice(),
             width: image_size.width,
-            pitch: 3 * image_size.width, // we use no padding between rows
+            pitch: C * image_size.width, // we use no padding between rows
             height: image_size.height,
-            format: turbojpeg::PixelFormat::RGB,
+            format,
         };
 
         // decompress the JPEG data
@@ -173,11 +296,234 @@ impl ImageDecoder {
 
         Ok(Image::new(image_size, pixels)?)
     }
+
+    /// Decodes the given JPEG data, choosing the channel count from the
+    /// image's own pixel format instead of assuming RGB.
+    ///
+    /// CMYK and YCCK (4-component) JPEGs are not supported: libjpeg-turbo has
+    /// no color-managed CMYK/YCCK -> RGB conversion, and always forces its
+    /// output colorspace to raw `JCS_CMYK` for 4-component input regardless of
+    /// the pixel format requested from it. Decoding such an image as RGBA
+    /// (which also happens to be 4 bytes/pixel) would silently mislabel raw,
+    /// Adobe-inverted ink percentages as RGBA with no indication anything is
+    /// wrong, so this returns [`JpegError::UnsupportedColorspace`] instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `jpeg_data` - The JPEG data to decode.
+    ///
+    /// # Returns
+    ///
+    /// The decoded image, as a [`DecodedImage`] carrying whichever channel
+    /// count matches the source JPEG's colorspace.
+    pub fn decode_auto(&mut self, jpeg_data: &[u8]) -> Result<DecodedImage, JpegError> {
+        let colorspace = self
+            .decompressor
+            .lock()
+            .unwrap()
+            .read_header(jpeg_data)?
+            .colorspace;
+
+        Ok(match colorspace {
+            turbojpeg::Colorspace::Gray => DecodedImage::Gray(self.decode::<1>(jpeg_data)?),
+            turbojpeg::Colorspace::CMYK | turbojpeg::Colorspace::YCCK => {
+                return Err(JpegError::UnsupportedColorspace(colorspace));
+            }
+            _ => DecodedImage::Rgb(self.decode::<3>(jpeg_data)?),
+        })
+    }
+
+    /// Decodes the given JPEG data at the largest scaling factor that still
+    /// fits within `target`.
+    ///
+    /// libjpeg-turbo can produce a fractional-scale (M/8 for M in 1..=8) output
+    /// directly from the DCT coefficients, which is much cheaper than decoding
+    /// the full image and resizing it afterwards. This is intended for
+    /// thumbnailers and tiled viewers that only need an approximate size.
+    ///
+    /// # Arguments
+    ///
+    /// * `jpeg_data` - The JPEG data to decode.
+    /// * `target` - The desired output size. The actual output size is the
+    ///   largest supported scaling factor whose dimensions are still `<=
+    ///   target` on both axes, so it may be smaller than requested. If no
+    ///   factor fits, the smallest factor is used, and the output is then
+    ///   larger than `target`.
+    ///
+    /// # Returns
+    ///
+    /// The decoded data as an `Image`, scaled down to at most `target` when
+    /// any supported factor fits, otherwise to the smallest factor.
+    pub fn decode_scaled(
+        &mut self,
+        jpeg_data: &[u8],
+        target: ImageSize,
+    ) -> Result<Image<u8, 3>, JpegError> {
+        let header_size = self.read_header(jpeg_data)?;
+
+        // pick the largest scaling factor that still fits within `target`; if
+        // none does (`target` is smaller than the smallest supported factor,
+        // typically 1/8), fall back to the smallest factor rather than the
+        // full resolution, since the caller explicitly asked for small output.
+        let scaling_factor = turbojpeg::scaling_factors()
+            .filter(|factor| {
+                factor.scale(header_size.width) <= target.width
+                    && factor.scale(header_size.height) <= target.height
+            })
+            .max_by_key(|factor| factor.scale(header_size.width))
+            .or_else(|| {
+                turbojpeg::scaling_factors().min_by_key(|factor| factor.scale(header_size.width))
+            })
+            .unwrap_or(turbojpeg::ScalingFactor::ONE);
+
+        let mut decompressor = self.decompressor.lock().unwrap();
+        decompressor.set_scaling_factor(scaling_factor)?;
+
+        let scaled_size = ImageSize {
+            width: scaling_factor.scale(header_size.width),
+            height: scaling_factor.scale(header_size.height),
+        };
+
+        // prepare a storage for the scaled pixel data
+        let mut pixels = vec![0u8; scaled_size.height * scaled_size.width * 3];
+
+        let buf = turbojpeg::Image {
+            pixels: pixels.as_mut_slice(),
+            width: scaled_size.width,
+            pitch: 3 * scaled_size.width,
+            height: scaled_size.height,
+            format: turbojpeg::PixelFormat::RGB,
+        };
+
+        let decode_result = decompressor.decompress(jpeg_data, buf);
+
+        // restore the default (unscaled) factor so later calls on this
+        // decoder are not silently affected by this one
+        decompressor.set_scaling_factor(turbojpeg::ScalingFactor::ONE)?;
+        decode_result?;
+
+        Ok(Image::new(scaled_size, pixels)?)
+    }
+
+    /// Decodes a rectangular region of the given JPEG data.
+    ///
+    /// turbojpeg's decompress call has no x/y offset of its own (its only
+    /// size control is one of the 8 IDCT scaling factors, as set by
+    /// [`Self::decode_scaled`]) — there is no way to ask
+    /// it to decode pixels starting partway through the image. So this first
+    /// losslessly crops the *compressed* JPEG bytes down to the MCU-aligned
+    /// band covering the request (rounding `x`/`y` down and expanding `w`/`h`
+    /// up to a multiple of the minimum coded unit for the image's chroma
+    /// subsampling, via [`crate::optimize::JpegTransformer`]'s lossless crop
+    /// transform), decodes that now-small JPEG in full, then slices the
+    /// exact `(x, y, w, h)` rectangle out of the result. This mirrors the
+    /// decoding-area APIs offered by other codecs (e.g. JPEG 2000's
+    /// reduction factor / decoding area) that let callers avoid
+    /// materializing pixels outside the area of interest.
+    ///
+    /// # Arguments
+    ///
+    /// * `jpeg_data` - The JPEG data to decode.
+    /// * `x` - Left edge of the region, in pixels.
+    /// * `y` - Top edge of the region, in pixels.
+    /// * `w` - Width of the region, in pixels.
+    /// * `h` - Height of the region, in pixels.
+    ///
+    /// # Returns
+    ///
+    /// The decoded pixel data for exactly the `(x, y, w, h)` rectangle.
+    pub fn decode_region(
+        &mut self,
+        jpeg_data: &[u8],
+        x: usize,
+        y: usize,
+        w: usize,
+        h: usize,
+    ) -> Result<Image<u8, 3>, JpegError> {
+        let header = self.decompressor.lock().unwrap().read_header(jpeg_data)?;
+
+        let out_of_bounds = || JpegError::RegionOutOfBounds {
+            x,
+            y,
+            w,
+            h,
+            image_width: header.width,
+            image_height: header.height,
+        };
+
+        let right = x.checked_add(w).ok_or_else(out_of_bounds)?;
+        let bottom = y.checked_add(h).ok_or_else(out_of_bounds)?;
+        if right > header.width || bottom > header.height {
+            return Err(out_of_bounds());
+        }
+
+        let (mcu_w, mcu_h) = mcu_size(header.subsamp);
+
+        // snap the requested rectangle to the MCU grid
+        let aligned_x = (x / mcu_w) * mcu_w;
+        let aligned_y = (y / mcu_h) * mcu_h;
+        let aligned_right = right.checked_add(mcu_w - 1).ok_or_else(out_of_bounds)? / mcu_w * mcu_w;
+        let aligned_bottom =
+            bottom.checked_add(mcu_h - 1).ok_or_else(out_of_bounds)? / mcu_h * mcu_h;
+        let aligned_w = aligned_right.min(header.width) - aligned_x;
+        let aligned_h = aligned_bottom.min(header.height) - aligned_y;
+
+        // losslessly crop the compressed bytes to the aligned band, then
+        // decode that (small) JPEG in full -- this is the actual region decode.
+        // No entropy re-optimization: the cropped JPEG is decoded to pixels
+        // one line below, so optimizing its Huffman tables would be wasted work.
+        let cropped = crate::optimize::JpegTransformer::new()?.transform(
+            jpeg_data,
+            crate::optimize::Transform::Crop {
+                x: aligned_x,
+                y: aligned_y,
+                w: aligned_w,
+                h: aligned_h,
+            },
+            false,
+        )?;
+        let band = self.decode::<3>(&cropped)?;
+
+        let mut pixels = vec![0u8; h * w * 3];
+        let row_offset = y - aligned_y;
+        let col_offset = (x - aligned_x) * 3;
+        let band_pitch = band.width() * 3;
+        let band_data = band.as_slice();
+        for row in 0..h {
+            let src_start = (row + row_offset) * band_pitch + col_offset;
+            let dst_start = row * w * 3;
+            pixels[dst_start..dst_start + w * 3]
+                .copy_from_slice(&band_data[src_start..src_start + w * 3]);
+        }
+
+        Ok(Image::new(
+            ImageSize {
+                width: w,
+                height: h,
+            },
+            pixels,
+        )?)
+    }
+}
+
+/// Returns the minimum coded unit (MCU) size, in pixels, for a given chroma
+/// subsampling scheme.
+fn mcu_size(subsamp: turbojpeg::Subsamp) -> (usize, usize) {
+    match subsamp {
+        turbojpeg::Subsamp::Sub411 => (32, 8),
+        turbojpeg::Subsamp::Sub420 => (16, 16),
+        turbojpeg::Subsamp::Sub422 => (16, 8),
+        turbojpeg::Subsamp::Sub440 => (8, 16),
+        turbojpeg::Subsamp::Sub444 => (8, 8),
+        turbojpeg::Subsamp::Gray => (8, 8),
+        turbojpeg::Subsamp::Unknown => (8, 8),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::jpeg::{ImageDecoder, ImageEncoder, JpegError};
+    use kornia_image::{Image, ImageSize};
 
     #[test]
     fn image_decoder() -> Result<(), JpegError> {
@@ -187,7 +533,7 @@ mod tests {
         assert_eq!(image_size.width, 258);
         assert_eq!(image_size.height, 195);
         // load the image as file and decode it
-        let image = ImageDecoder::new()?.decode(&jpeg_data)?;
+        let image = ImageDecoder::new()?.decode::<3>(&jpeg_data)?;
         assert_eq!(image.size().width, 258);
         assert_eq!(image.size().height, 195);
         assert_eq!(image.num_channels(), 3);
@@ -197,12 +543,156 @@ mod tests {
     #[test]
     fn image_encoder() -> Result<(), Box<dyn std::error::Error>> {
         let jpeg_data_fs = std::fs::read("../../tests/data/dog.jpeg")?;
-        let image = ImageDecoder::new()?.decode(&jpeg_data_fs)?;
-        let jpeg_data = ImageEncoder::new()?.encode(&image)?;
-        let image_back = ImageDecoder::new()?.decode(&jpeg_data)?;
+        let image = ImageDecoder::new()?.decode::<3>(&jpeg_data_fs)?;
+        let jpeg_data = ImageEncoder::new()?.encode::<3>(&image)?;
+        let image_back = ImageDecoder::new()?.decode::<3>(&jpeg_data)?;
         assert_eq!(image_back.size().width, 258);
         assert_eq!(image_back.size().height, 195);
         assert_eq!(image_back.num_channels(), 3);
         Ok(())
     }
+
+    #[test]
+    fn jpeg_roundtrip_grayscale() -> Result<(), JpegError> {
+        let image = Image::<u8, 1>::new(
+            ImageSize {
+                width: 16,
+                height: 16,
+            },
+            vec![128u8; 16 * 16],
+        )?;
+
+        let jpeg_data = ImageEncoder::new()?.encode::<1>(&image)?;
+        let image_back = ImageDecoder::new()?.decode::<1>(&jpeg_data)?;
+        assert_eq!(image_back.size().width, 16);
+        assert_eq!(image_back.size().height, 16);
+        assert_eq!(image_back.num_channels(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn jpeg_roundtrip_rgba() -> Result<(), JpegError> {
+        let image = Image::<u8, 4>::new(
+            ImageSize {
+                width: 16,
+                height: 16,
+            },
+            vec![200u8; 16 * 16 * 4],
+        )?;
+
+        let jpeg_data = ImageEncoder::new()?.encode::<4>(&image)?;
+        let image_back = ImageDecoder::new()?.decode::<4>(&jpeg_data)?;
+        assert_eq!(image_back.size().width, 16);
+        assert_eq!(image_back.size().height, 16);
+        assert_eq!(image_back.num_channels(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn image_encoder_decoder_auto() -> Result<(), Box<dyn std::error::Error>> {
+        let jpeg_data_fs = std::fs::read("../../tests/data/dog.jpeg")?;
+        let image = ImageDecoder::new()?.decode_auto(&jpeg_data_fs)?;
+        assert_eq!(image.num_channels(), 3);
+
+        let mut encoder = ImageEncoder::new()?;
+        encoder.set_subsamp(turbojpeg::Subsamp::Sub444)?;
+        encoder.set_progressive(true)?;
+        encoder.set_optimize(true)?;
+
+        let crate::jpeg::DecodedImage::Rgb(rgb_image) = image else {
+            panic!("expected an RGB image");
+        };
+        let jpeg_data = encoder.encode::<3>(&rgb_image)?;
+        let image_back = ImageDecoder::new()?.decode::<3>(&jpeg_data)?;
+        assert_eq!(image_back.size().width, 258);
+        assert_eq!(image_back.size().height, 195);
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_colorspace_error_message() {
+        // No CMYK/YCCK fixture is checked in, so `decode_auto` is not called
+        // here: this only checks the error value it returns for such input
+        // and that error's message.
+        let err = JpegError::UnsupportedColorspace(turbojpeg::Colorspace::CMYK);
+        assert!(matches!(err, JpegError::UnsupportedColorspace(_)));
+        assert!(err.to_string().contains("Unsupported colorspace"));
+    }
+
+    #[test]
+    fn decode_scaled_below_smallest_factor_still_downscales() -> Result<(), JpegError> {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+
+        // 258x195 divided by the smallest turbojpeg factor (1/8) is ~32x24,
+        // so asking for something even smaller must not fall back to full res.
+        let image = ImageDecoder::new()?.decode_scaled(
+            &jpeg_data,
+            kornia_image::ImageSize {
+                width: 8,
+                height: 8,
+            },
+        )?;
+        assert!(image.size().width < 258);
+        assert!(image.size().height < 195);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_region_past_image_edge_is_an_error() {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+        let result = ImageDecoder::new()
+            .unwrap()
+            .decode_region(&jpeg_data, 200, 150, 100, 100);
+        assert!(matches!(result, Err(JpegError::RegionOutOfBounds { .. })));
+    }
+
+    #[test]
+    fn decode_region_overflowing_bounds_is_an_error_not_a_panic() {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+        let result = ImageDecoder::new().unwrap().decode_region(
+            &jpeg_data,
+            usize::MAX,
+            usize::MAX,
+            usize::MAX,
+            usize::MAX,
+        );
+        assert!(matches!(result, Err(JpegError::RegionOutOfBounds { .. })));
+    }
+
+    #[test]
+    fn decode_region_uneven_edge_tile() -> Result<(), JpegError> {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+        let header_size = ImageDecoder::new()?.read_header(&jpeg_data)?;
+
+        // a tile whose size does not evenly divide the image, flush with the
+        // bottom-right corner, to exercise the aligned-band clamping.
+        let (w, h) = (50, 40);
+        let (x, y) = (header_size.width - w, header_size.height - h);
+        let image = ImageDecoder::new()?.decode_region(&jpeg_data, x, y, w, h)?;
+        assert_eq!(image.size().width, w);
+        assert_eq!(image.size().height, h);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_region_matches_a_manual_crop_of_the_full_decode() -> Result<(), JpegError> {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+        let (x, y, w, h) = (40, 20, 60, 50);
+
+        let region = ImageDecoder::new()?.decode_region(&jpeg_data, x, y, w, h)?;
+
+        let full = ImageDecoder::new()?.decode::<3>(&jpeg_data)?;
+        let full_pitch = full.width() * 3;
+        let full_data = full.as_slice();
+        let mut expected = vec![0u8; h * w * 3];
+        for row in 0..h {
+            let src_start = (row + y) * full_pitch + x * 3;
+            let dst_start = row * w * 3;
+            expected[dst_start..dst_start + w * 3]
+                .copy_from_slice(&full_data[src_start..src_start + w * 3]);
+        }
+
+        assert_eq!(region.as_slice(), expected.as_slice());
+        Ok(())
+    }
 }
@@ -0,0 +1,374 @@
+use std::sync::{Arc, Mutex};
+
+use jpegxl_rs::{
+    decode::BasicInfo,
+    decoder_builder,
+    encode::{EncoderFrame, EncoderResult, EncoderSpeed},
+    encoder_builder, Decoder, EncodeError, Encoder,
+};
+
+use kornia_image::{Image, ImageError, ImageSize};
+
+/// Error types for the JPEG XL module.
+#[derive(thiserror::Error, Debug)]
+pub enum JpegXlError {
+    /// Error coming from the underlying `jpegxl-rs` decoder.
+    #[error("Something went wrong decoding the JPEG XL image")]
+    DecodeError(#[from] jpegxl_rs::DecodeError),
+
+    /// Error coming from the underlying `jpegxl-rs` encoder.
+    #[error("Something went wrong encoding the JPEG XL image")]
+    EncodeError(#[from] EncodeError),
+
+    /// Error when the decoded image has no pixel data.
+    #[error("Decoded JPEG XL image has no pixel data")]
+    EmptyImage,
+
+    /// Error when the requested channel count has no corresponding pixel layout.
+    #[error("Unsupported number of channels: {0} (expected 1, 3 or 4)")]
+    UnsupportedChannelCount(usize),
+
+    /// Error when the file's actual channel count doesn't match the one requested by the caller.
+    #[error("JPEG XL image has {actual} channel(s), but {expected} were requested")]
+    ChannelCountMismatch {
+        /// The channel count requested by the caller (the decoder's `C`).
+        expected: usize,
+        /// The channel count actually present in the JPEG XL image.
+        actual: usize,
+    },
+
+    /// Error to create the image.
+    #[error("Failed to create image")]
+    ImageCreationError(#[from] ImageError),
+}
+
+/// A JPEG XL encoder using `libjxl`.
+pub struct ImageEncoder {
+    distance: f32,
+    speed: EncoderSpeed,
+}
+
+/// A JPEG XL decoder using `libjxl`.
+pub struct ImageDecoder;
+
+impl Default for ImageEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for ImageDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageEncoder {
+    /// Creates a new `ImageEncoder` at the default (visually lossless) distance.
+    ///
+    /// # Returns
+    ///
+    /// A new `ImageEncoder` instance.
+    pub fn new() -> Self {
+        Self {
+            distance: 1.0,
+            speed: EncoderSpeed::Squirrel,
+        }
+    }
+
+    /// Sets the target butteraugli distance (0.0 = mathematically lossless,
+    /// ~1.0 = visually lossless, higher values trade quality for file size).
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The target butteraugli distance.
+    pub fn set_distance(&mut self, distance: f32) {
+        self.distance = distance;
+    }
+
+    /// Sets the encoder effort. Higher effort spends more time for a smaller file.
+    ///
+    /// # Arguments
+    ///
+    /// * `speed` - The encoder effort/speed tier.
+    pub fn set_speed(&mut self, speed: EncoderSpeed) {
+        self.speed = speed;
+    }
+
+    /// Encodes an 8-bit image into a JPEG XL byte stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to encode. 1 channel is grayscale, 3 is RGB and 4 is RGBA.
+    ///
+    /// # Returns
+    ///
+    /// The encoded data as `Vec<u8>`.
+    pub fn encode<const C: usize>(&mut self, image: &Image<u8, C>) -> Result<Vec<u8>, JpegXlError> {
+        let mut encoder: Encoder = encoder_builder()
+            .distance(self.distance)
+            .speed(self.speed)
+            .build()?;
+
+        let frame = EncoderFrame::new(image.as_slice()).num_channels(C as u32);
+        let result: EncoderResult<u8> =
+            encoder.encode_frame(&frame, image.width() as u32, image.height() as u32)?;
+
+        Ok(result.data)
+    }
+
+    /// Encodes a 16-bit image into a JPEG XL byte stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to encode. 1 channel is grayscale, 3 is RGB and 4 is RGBA.
+    ///
+    /// # Returns
+    ///
+    /// The encoded data as `Vec<u8>`.
+    pub fn encode16<const C: usize>(
+        &mut self,
+        image: &Image<u16, C>,
+    ) -> Result<Vec<u8>, JpegXlError> {
+        let mut encoder: Encoder = encoder_builder()
+            .distance(self.distance)
+            .speed(self.speed)
+            .build()?;
+
+        let frame = EncoderFrame::new(image.as_slice()).num_channels(C as u32);
+        let result: EncoderResult<u16> =
+            encoder.encode_frame(&frame, image.width() as u32, image.height() as u32)?;
+
+        Ok(result.data)
+    }
+
+    /// Repackages an existing JPEG byte stream into JPEG XL, losslessly: the
+    /// original JPEG can later be reconstructed bit-exact with
+    /// [`ImageDecoder::to_jpeg_bytes`]. This typically shrinks the file by
+    /// roughly 20% with no further generation loss.
+    ///
+    /// # Arguments
+    ///
+    /// * `jpeg_data` - The JPEG data to repackage.
+    ///
+    /// # Returns
+    ///
+    /// The JPEG XL data as `Vec<u8>`.
+    pub fn from_jpeg_bytes(&mut self, jpeg_data: &[u8]) -> Result<Vec<u8>, JpegXlError> {
+        let mut encoder: Encoder = encoder_builder().build()?;
+        Ok(encoder.encode_jpeg(jpeg_data)?.data)
+    }
+}
+
+impl ImageDecoder {
+    /// Creates a new `ImageDecoder`.
+    ///
+    /// # Returns
+    ///
+    /// A new `ImageDecoder` instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Reads the size of a JPEG XL image without decoding its pixels.
+    ///
+    /// This stops at libjxl's basic-info event, so it does not run the
+    /// (comparatively expensive) pixel decode that `decode`/`decode16` do.
+    ///
+    /// # Arguments
+    ///
+    /// * `jxl_data` - The JPEG XL data to read the header from.
+    ///
+    /// # Returns
+    ///
+    /// The image size.
+    pub fn read_header(&mut self, jxl_data: &[u8]) -> Result<ImageSize, JpegXlError> {
+        let info = self.read_info(jxl_data)?;
+
+        Ok(ImageSize {
+            width: info.xsize as usize,
+            height: info.ysize as usize,
+        })
+    }
+
+    /// Reads the basic info (size, channel layout, ...) of a JPEG XL image
+    /// without decoding its pixels.
+    fn read_info(&mut self, jxl_data: &[u8]) -> Result<BasicInfo, JpegXlError> {
+        let decoder: Decoder = decoder_builder().build()?;
+        decoder.basic_info(jxl_data)?.ok_or(JpegXlError::EmptyImage)
+    }
+
+    /// Decodes a JPEG XL byte stream into an 8-bit image with `C` channels.
+    ///
+    /// # Arguments
+    ///
+    /// * `jxl_data` - The JPEG XL data to decode.
+    ///
+    /// # Returns
+    ///
+    /// The decoded data as an `Image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JpegXlError::ChannelCountMismatch`] if the image's own
+    /// color + alpha channel count does not match `C`.
+    pub fn decode<const C: usize>(&mut self, jxl_data: &[u8]) -> Result<Image<u8, C>, JpegXlError> {
+        check_channel_count(&self.read_info(jxl_data)?, C)?;
+
+        let decoder: Decoder = decoder_builder().build()?;
+        let (metadata, pixels) = decoder.decode_with::<u8>(jxl_data)?;
+
+        let image_size = ImageSize {
+            width: metadata.width as usize,
+            height: metadata.height as usize,
+        };
+
+        Ok(Image::new(image_size, pixels)?)
+    }
+
+    /// Decodes a JPEG XL byte stream into a 16-bit image with `C` channels.
+    ///
+    /// # Arguments
+    ///
+    /// * `jxl_data` - The JPEG XL data to decode.
+    ///
+    /// # Returns
+    ///
+    /// The decoded data as an `Image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JpegXlError::ChannelCountMismatch`] if the image's own
+    /// color + alpha channel count does not match `C`.
+    pub fn decode16<const C: usize>(
+        &mut self,
+        jxl_data: &[u8],
+    ) -> Result<Image<u16, C>, JpegXlError> {
+        check_channel_count(&self.read_info(jxl_data)?, C)?;
+
+        let decoder: Decoder = decoder_builder().build()?;
+        let (metadata, pixels) = decoder.decode_with::<u16>(jxl_data)?;
+
+        let image_size = ImageSize {
+            width: metadata.width as usize,
+            height: metadata.height as usize,
+        };
+
+        Ok(Image::new(image_size, pixels)?)
+    }
+
+    /// Reconstructs the original, bit-exact JPEG bytes from a JPEG XL stream
+    /// that was produced by [`ImageEncoder::from_jpeg_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `jxl_data` - The JPEG XL data to reconstruct the JPEG from.
+    ///
+    /// # Returns
+    ///
+    /// The reconstructed JPEG data as `Vec<u8>`.
+    pub fn to_jpeg_bytes(&mut self, jxl_data: &[u8]) -> Result<Vec<u8>, JpegXlError> {
+        let decoder: Decoder = decoder_builder().reconstruct_jpeg(true).build()?;
+        let (_, jpeg_data) = decoder
+            .reconstruct_jpeg(jxl_data)?
+            .ok_or(JpegXlError::EmptyImage)?;
+
+        Ok(jpeg_data)
+    }
+}
+
+/// Checks that the channel count implied by a JPEG XL image's color + alpha
+/// channels matches `expected`.
+///
+/// `decode`/`decode16` always return pixels in the file's native channel
+/// count (`jpegxl-rs` has no notion of requesting a different layout), so
+/// this is the only thing standing between a caller's chosen `C` and a
+/// pixel buffer of the wrong length silently reaching [`Image::new`].
+fn check_channel_count(info: &BasicInfo, expected: usize) -> Result<(), JpegXlError> {
+    let channels = info.num_color_channels as usize + if info.alpha_bits > 0 { 1 } else { 0 };
+
+    if channels != expected {
+        return Err(JpegXlError::ChannelCountMismatch {
+            expected,
+            actual: channels,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jpegxl::{ImageDecoder, ImageEncoder, JpegXlError};
+    use kornia_image::{Image, ImageSize};
+
+    #[test]
+    fn jpegxl_roundtrip() -> Result<(), JpegXlError> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![42u8; 4 * 4 * 3],
+        )?;
+
+        let jxl_data = ImageEncoder::new().encode::<3>(&image)?;
+        let image_back = ImageDecoder::new().decode::<3>(&jxl_data)?;
+        assert_eq!(image_back.size().width, 4);
+        assert_eq!(image_back.size().height, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_rejects_mismatched_channel_count() -> Result<(), JpegXlError> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![42u8; 4 * 4 * 3],
+        )?;
+
+        let jxl_data = ImageEncoder::new().encode::<3>(&image)?;
+        let err = ImageDecoder::new().decode::<1>(&jxl_data).unwrap_err();
+        assert!(matches!(
+            err,
+            JpegXlError::ChannelCountMismatch {
+                expected: 1,
+                actual: 3
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "JPEG XL image has 3 channel(s), but 1 were requested"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_header_matches_decoded_size() -> Result<(), JpegXlError> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![7u8; 4 * 4 * 3],
+        )?;
+
+        let jxl_data = ImageEncoder::new().encode::<3>(&image)?;
+        let header_size = ImageDecoder::new().read_header(&jxl_data)?;
+        assert_eq!(header_size.width, 4);
+        assert_eq!(header_size.height, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn jpegxl_lossless_jpeg_recompression() -> Result<(), JpegXlError> {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+
+        let jxl_data = ImageEncoder::new().from_jpeg_bytes(&jpeg_data)?;
+        let jpeg_back = ImageDecoder::new().to_jpeg_bytes(&jxl_data)?;
+        assert_eq!(jpeg_back, jpeg_data);
+        Ok(())
+    }
+}
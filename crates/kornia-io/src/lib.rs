@@ -0,0 +1,18 @@
+//! # kornia-io
+//!
+//! Image encoding and decoding utilities for the `kornia` ecosystem.
+
+/// JPEG encoding and decoding backed by `turbojpeg`.
+pub mod jpeg;
+
+/// TIFF encoding and decoding backed by the `tiff` crate.
+pub mod tiff;
+
+/// Unified, format-detecting image reader.
+pub mod io;
+
+/// Lossless JPEG recompression and transforms.
+pub mod optimize;
+
+/// JPEG XL encoding and decoding backed by `libjxl`.
+pub mod jpegxl;
@@ -0,0 +1,221 @@
+use std::sync::{Arc, Mutex};
+use turbojpeg;
+
+/// Error types for the optimize module.
+#[derive(thiserror::Error, Debug)]
+pub enum OptimizeError {
+    /// Error when the JPEG transformer cannot be created or a transform fails.
+    #[error("Something went wrong with the JPEG lossless transform")]
+    TurboJpegError(#[from] turbojpeg::Error),
+}
+
+/// A lossless JPEG transform, applied directly to the entropy-coded data
+/// without a full decode/re-encode (and therefore without any quality loss).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// No geometric transform; only re-optimizes the entropy coding.
+    None,
+    /// Rotate 90 degrees clockwise.
+    Rotate90,
+    /// Rotate 180 degrees.
+    Rotate180,
+    /// Rotate 270 degrees clockwise.
+    Rotate270,
+    /// Flip horizontally (mirror left-right).
+    FlipHorizontal,
+    /// Flip vertically (mirror top-bottom).
+    FlipVertical,
+    /// Losslessly crop to an MCU-aligned rectangle.
+    Crop {
+        /// Left edge of the crop, in pixels. Rounded down to the MCU grid.
+        x: usize,
+        /// Top edge of the crop, in pixels. Rounded down to the MCU grid.
+        y: usize,
+        /// Width of the crop, in pixels.
+        w: usize,
+        /// Height of the crop, in pixels.
+        h: usize,
+    },
+}
+
+impl Transform {
+    fn op(&self) -> turbojpeg::TransformOp {
+        match self {
+            Transform::None => turbojpeg::TransformOp::None,
+            Transform::Rotate90 => turbojpeg::TransformOp::Rot90,
+            Transform::Rotate180 => turbojpeg::TransformOp::Rot180,
+            Transform::Rotate270 => turbojpeg::TransformOp::Rot270,
+            Transform::FlipHorizontal => turbojpeg::TransformOp::Hflip,
+            Transform::FlipVertical => turbojpeg::TransformOp::Vflip,
+            Transform::Crop { .. } => turbojpeg::TransformOp::None,
+        }
+    }
+
+    fn crop(&self) -> Option<turbojpeg::TransformCrop> {
+        match *self {
+            Transform::Crop { x, y, w, h } => Some(turbojpeg::TransformCrop {
+                x,
+                y,
+                w: Some(w),
+                h: Some(h),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A lossless JPEG transformer using the turbojpeg library.
+pub struct JpegTransformer {
+    /// The turbojpeg transformer.
+    pub transformer: Arc<Mutex<turbojpeg::Transformer>>,
+}
+
+impl Default for JpegTransformer {
+    fn default() -> Self {
+        match Self::new() {
+            Ok(transformer) => transformer,
+            Err(e) => panic!("Failed to create JpegTransformer: {}", e),
+        }
+    }
+}
+
+impl JpegTransformer {
+    /// Creates a new `JpegTransformer`.
+    ///
+    /// # Returns
+    ///
+    /// A new `JpegTransformer` instance.
+    pub fn new() -> Result<Self, OptimizeError> {
+        let transformer = turbojpeg::Transformer::new()?;
+        Ok(Self {
+            transformer: Arc::new(Mutex::new(transformer)),
+        })
+    }
+
+    /// Applies a lossless transform to the given JPEG data.
+    ///
+    /// `lossless_recompress` additionally re-optimizes the entropy coding
+    /// (progressive, optimized Huffman tables) of the transformed output,
+    /// which is what makes [`optimize_jpeg`] and [`transform_jpeg`] shrink
+    /// the file. Callers that only want the geometric effect of `transform`
+    /// (e.g. cropping a band out of a JPEG to decode it) should pass `false`
+    /// so they don't pay for re-optimizing entropy coding they are about to
+    /// throw away by decoding it back to pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `jpeg_data` - The JPEG data to transform.
+    /// * `transform` - The transform to apply.
+    /// * `lossless_recompress` - Whether to also re-optimize the entropy
+    ///   coding of the transformed output.
+    ///
+    /// # Returns
+    ///
+    /// The transformed JPEG data as `Vec<u8>`.
+    pub fn transform(
+        &mut self,
+        jpeg_data: &[u8],
+        transform: Transform,
+        lossless_recompress: bool,
+    ) -> Result<Vec<u8>, OptimizeError> {
+        let request = turbojpeg::Transform {
+            op: transform.op(),
+            crop: transform.crop(),
+            optimize: lossless_recompress,
+            progressive: lossless_recompress,
+            ..Default::default()
+        };
+
+        Ok(self
+            .transformer
+            .lock()
+            .unwrap()
+            .transform(&request, jpeg_data)?)
+    }
+}
+
+/// Losslessly shrinks a JPEG byte stream by re-encoding its entropy-coded
+/// data as progressive, optimized Huffman — no decode/re-encode of the pixel
+/// data takes place, so there is no quality loss. Returns the original
+/// bytes unchanged if the optimized version would be larger.
+///
+/// # Arguments
+///
+/// * `jpeg_data` - The JPEG data to optimize.
+///
+/// # Returns
+///
+/// The optimized JPEG data as `Vec<u8>`, or the original bytes if optimizing
+/// did not shrink them.
+pub fn optimize_jpeg(jpeg_data: &[u8]) -> Result<Vec<u8>, OptimizeError> {
+    let optimized = JpegTransformer::new()?.transform(jpeg_data, Transform::None, true)?;
+
+    Ok(if optimized.len() < jpeg_data.len() {
+        optimized
+    } else {
+        jpeg_data.to_vec()
+    })
+}
+
+/// Losslessly transforms a JPEG byte stream (rotate, flip or MCU-aligned crop).
+///
+/// # Arguments
+///
+/// * `jpeg_data` - The JPEG data to transform.
+/// * `transform` - The transform to apply.
+///
+/// # Returns
+///
+/// The transformed JPEG data as `Vec<u8>`.
+pub fn transform_jpeg(jpeg_data: &[u8], transform: Transform) -> Result<Vec<u8>, OptimizeError> {
+    JpegTransformer::new()?.transform(jpeg_data, transform, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{optimize_jpeg, transform_jpeg, Transform};
+    use crate::jpeg::ImageDecoder;
+
+    #[test]
+    fn optimize_does_not_grow_the_file() -> Result<(), Box<dyn std::error::Error>> {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+        let optimized = optimize_jpeg(&jpeg_data)?;
+        assert!(optimized.len() <= jpeg_data.len());
+
+        // The entropy coding changed, but the pixels must not have.
+        let original = ImageDecoder::new()?.decode::<3>(&jpeg_data)?;
+        let roundtripped = ImageDecoder::new()?.decode::<3>(&optimized)?;
+        assert_eq!(roundtripped.size().width, original.size().width);
+        assert_eq!(roundtripped.size().height, original.size().height);
+        assert_eq!(roundtripped.as_slice(), original.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn rotate_90_is_lossless_and_valid() -> Result<(), Box<dyn std::error::Error>> {
+        let jpeg_data = std::fs::read("../../tests/data/dog.jpeg").unwrap();
+        let rotated = transform_jpeg(&jpeg_data, Transform::Rotate90)?;
+
+        let original = ImageDecoder::new()?.decode::<3>(&jpeg_data)?;
+        let rotated_image = ImageDecoder::new()?.decode::<3>(&rotated)?;
+
+        let (w, h) = (original.size().width, original.size().height);
+        assert_eq!(rotated_image.size().width, h);
+        assert_eq!(rotated_image.size().height, w);
+
+        // A 90-degree clockwise rotation maps original (row, col) to
+        // rotated (row = col, col = h - 1 - row); check it pixel-for-pixel
+        // rather than just trusting turbojpeg not to have corrupted data.
+        let original_data = original.as_slice();
+        let rotated_data = rotated_image.as_slice();
+        let rotated_pitch = h * 3;
+        for row in 0..h {
+            for col in 0..w {
+                let expected = &original_data[(row * w + col) * 3..][..3];
+                let actual = &rotated_data[col * rotated_pitch + (h - 1 - row) * 3..][..3];
+                assert_eq!(actual, expected, "pixel mismatch at ({row}, {col})");
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,456 @@
+use std::io::Cursor;
+
+use tiff::decoder::DecodingResult;
+use tiff::encoder::colortype::{Gray16, Gray8, RGB16, RGB8};
+use tiff::encoder::compression::{Deflate, Lzw, Packbits, Uncompressed};
+use tiff::encoder::{ColorType, TiffEncoder};
+
+use kornia_image::{Image, ImageError, ImageSize};
+
+/// Error types for the TIFF module.
+#[derive(thiserror::Error, Debug)]
+pub enum TiffError {
+    /// Error coming from the underlying `tiff` codec.
+    #[error("Something went wrong with the TIFF codec")]
+    TiffCodecError(#[from] tiff::TiffError),
+
+    /// Error to create the image.
+    #[error("Failed to create image")]
+    ImageCreationError(#[from] ImageError),
+
+    /// Error when the decoded color type has no corresponding channel layout.
+    #[error("Unsupported TIFF color type: {0:?}")]
+    UnsupportedColorType(tiff::ColorType),
+
+    /// Error when the requested channel count has no corresponding TIFF color type.
+    #[error("Unsupported number of channels: {0} (expected 1 or 3)")]
+    UnsupportedChannelCount(usize),
+
+    /// Error when the file's actual channel count doesn't match the one requested by the caller.
+    #[error("TIFF file has {actual} channel(s), but {expected} were requested")]
+    ChannelCountMismatch {
+        /// The channel count requested by the caller (the decoder's `C`).
+        expected: usize,
+        /// The channel count actually present in the TIFF file.
+        actual: usize,
+    },
+}
+
+/// The compression scheme used when writing a TIFF file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TiffCompression {
+    /// No compression.
+    #[default]
+    None,
+    /// PackBits run-length encoding.
+    Packbits,
+    /// LZW (Lempel-Ziv-Welch) compression.
+    Lzw,
+    /// Deflate (zlib) compression.
+    Deflate,
+}
+
+/// A TIFF encoder, writing baseline TIFF with a selectable compression scheme.
+pub struct ImageEncoder {
+    /// The compression scheme applied to the written image.
+    pub compression: TiffCompression,
+}
+
+/// A TIFF decoder.
+pub struct ImageDecoder;
+
+impl Default for ImageEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for ImageDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of the ImageEncoder struct.
+impl ImageEncoder {
+    /// Creates a new `ImageEncoder` with no compression.
+    ///
+    /// # Returns
+    ///
+    /// A new `ImageEncoder` instance.
+    pub fn new() -> Self {
+        Self {
+            compression: TiffCompression::None,
+        }
+    }
+
+    /// Sets the compression scheme of the encoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - The compression scheme to use.
+    pub fn set_compression(&mut self, compression: TiffCompression) {
+        self.compression = compression;
+    }
+
+    /// Encodes an 8-bit image into a TIFF file.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to encode. 1 channel is written as grayscale, 3 as RGB.
+    ///
+    /// # Returns
+    ///
+    /// The encoded data as `Vec<u8>`.
+    pub fn encode<const C: usize>(&mut self, image: &Image<u8, C>) -> Result<Vec<u8>, TiffError> {
+        let mut buf = Vec::new();
+        let width = image.width() as u32;
+        let height = image.height() as u32;
+        let mut encoder = TiffEncoder::new(Cursor::new(&mut buf))?;
+
+        match C {
+            1 => write_image::<_, Gray8>(
+                &mut encoder,
+                width,
+                height,
+                image.as_slice(),
+                self.compression,
+            )?,
+            3 => write_image::<_, RGB8>(
+                &mut encoder,
+                width,
+                height,
+                image.as_slice(),
+                self.compression,
+            )?,
+            _ => return Err(TiffError::UnsupportedChannelCount(C)),
+        }
+
+        Ok(buf)
+    }
+
+    /// Encodes a 16-bit image into a TIFF file.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to encode. 1 channel is written as grayscale, 3 as RGB.
+    ///
+    /// # Returns
+    ///
+    /// The encoded data as `Vec<u8>`.
+    pub fn encode16<const C: usize>(
+        &mut self,
+        image: &Image<u16, C>,
+    ) -> Result<Vec<u8>, TiffError> {
+        let mut buf = Vec::new();
+        let width = image.width() as u32;
+        let height = image.height() as u32;
+        let mut encoder = TiffEncoder::new(Cursor::new(&mut buf))?;
+
+        match C {
+            1 => write_image::<_, Gray16>(
+                &mut encoder,
+                width,
+                height,
+                image.as_slice(),
+                self.compression,
+            )?,
+            3 => write_image::<_, RGB16>(
+                &mut encoder,
+                width,
+                height,
+                image.as_slice(),
+                self.compression,
+            )?,
+            _ => return Err(TiffError::UnsupportedChannelCount(C)),
+        }
+
+        Ok(buf)
+    }
+}
+
+/// Writes a single image of color type `Color` into `encoder`, using `compression`.
+fn write_image<W: std::io::Write + std::io::Seek, Color: ColorType>(
+    encoder: &mut TiffEncoder<W>,
+    width: u32,
+    height: u32,
+    data: &[Color::Inner],
+    compression: TiffCompression,
+) -> Result<(), TiffError> {
+    let image = encoder.new_image::<Color>(width, height)?;
+    match compression {
+        TiffCompression::None => image.with_compression(Uncompressed)?.write_data(data)?,
+        TiffCompression::Packbits => image.with_compression(Packbits)?.write_data(data)?,
+        TiffCompression::Lzw => image.with_compression(Lzw)?.write_data(data)?,
+        TiffCompression::Deflate => image
+            .with_compression(Deflate::default())?
+            .write_data(data)?,
+    }
+    Ok(())
+}
+
+/// Implementation of the ImageDecoder struct.
+impl ImageDecoder {
+    /// Creates a new `ImageDecoder`.
+    ///
+    /// # Returns
+    ///
+    /// A new `ImageDecoder` instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Reads the header of a TIFF image.
+    ///
+    /// # Arguments
+    ///
+    /// * `tiff_data` - The TIFF data to read the header from.
+    ///
+    /// # Returns
+    ///
+    /// The image size, as recovered from the IFD's width/height tags.
+    pub fn read_header(&mut self, tiff_data: &[u8]) -> Result<ImageSize, TiffError> {
+        let mut decoder = tiff::decoder::Decoder::new(Cursor::new(tiff_data))?;
+        let (width, height) = decoder.dimensions()?;
+
+        Ok(ImageSize {
+            width: width as usize,
+            height: height as usize,
+        })
+    }
+
+    /// Decodes an 8-bit TIFF image.
+    ///
+    /// # Arguments
+    ///
+    /// * `tiff_data` - The TIFF data to decode.
+    ///
+    /// # Returns
+    ///
+    /// The decoded data as an `Image`, with `C` channels (1 for Gray8, 3 for RGB8).
+    pub fn decode<const C: usize>(&mut self, tiff_data: &[u8]) -> Result<Image<u8, C>, TiffError> {
+        let mut decoder = tiff::decoder::Decoder::new(Cursor::new(tiff_data))?;
+        let (width, height) = decoder.dimensions()?;
+        let color_type = decoder.colortype()?;
+
+        let pixels = match decoder.read_image()? {
+            DecodingResult::U8(pixels) => pixels,
+            _ => return Err(TiffError::UnsupportedColorType(color_type)),
+        };
+
+        let image_size = ImageSize {
+            width: width as usize,
+            height: height as usize,
+        };
+        check_channel_count(color_type, C)?;
+
+        Ok(Image::new(image_size, pixels)?)
+    }
+
+    /// Decodes a 16-bit TIFF image.
+    ///
+    /// # Arguments
+    ///
+    /// * `tiff_data` - The TIFF data to decode.
+    ///
+    /// # Returns
+    ///
+    /// The decoded data as an `Image`, with `C` channels (1 for Gray16, 3 for RGB16).
+    pub fn decode16<const C: usize>(
+        &mut self,
+        tiff_data: &[u8],
+    ) -> Result<Image<u16, C>, TiffError> {
+        let mut decoder = tiff::decoder::Decoder::new(Cursor::new(tiff_data))?;
+        let (width, height) = decoder.dimensions()?;
+        let color_type = decoder.colortype()?;
+
+        let pixels = match decoder.read_image()? {
+            DecodingResult::U16(pixels) => pixels,
+            _ => return Err(TiffError::UnsupportedColorType(color_type)),
+        };
+
+        let image_size = ImageSize {
+            width: width as usize,
+            height: height as usize,
+        };
+        check_channel_count(color_type, C)?;
+
+        Ok(Image::new(image_size, pixels)?)
+    }
+
+    /// Decodes a TIFF image, choosing the channel count and bit depth from
+    /// its own color type instead of assuming RGB8.
+    ///
+    /// # Arguments
+    ///
+    /// * `tiff_data` - The TIFF data to decode.
+    ///
+    /// # Returns
+    ///
+    /// The decoded image, as a [`DecodedImage`] matching the source's color type.
+    pub fn decode_auto(&mut self, tiff_data: &[u8]) -> Result<DecodedImage, TiffError> {
+        let color_type = tiff::decoder::Decoder::new(Cursor::new(tiff_data))?.colortype()?;
+
+        Ok(match color_type {
+            tiff::ColorType::Gray(8) => DecodedImage::Gray8(self.decode::<1>(tiff_data)?),
+            tiff::ColorType::Gray(16) => DecodedImage::Gray16(self.decode16::<1>(tiff_data)?),
+            tiff::ColorType::RGB(8) => DecodedImage::Rgb8(self.decode::<3>(tiff_data)?),
+            tiff::ColorType::RGB(16) => DecodedImage::Rgb16(self.decode16::<3>(tiff_data)?),
+            other => return Err(TiffError::UnsupportedColorType(other)),
+        })
+    }
+}
+
+/// A decoded TIFF image whose channel count and bit depth match the source's
+/// own color type, as picked by [`ImageDecoder::decode_auto`].
+pub enum DecodedImage {
+    /// An 8-bit single-channel (grayscale) image.
+    Gray8(Image<u8, 1>),
+    /// An 8-bit three-channel (RGB) image.
+    Rgb8(Image<u8, 3>),
+    /// A 16-bit single-channel (grayscale) image.
+    Gray16(Image<u16, 1>),
+    /// A 16-bit three-channel (RGB) image.
+    Rgb16(Image<u16, 3>),
+}
+
+/// Checks that the channel count implied by a TIFF color type matches `expected`.
+fn check_channel_count(color_type: tiff::ColorType, expected: usize) -> Result<(), TiffError> {
+    let channels = match color_type {
+        tiff::ColorType::Gray(_) => 1,
+        tiff::ColorType::RGB(_) => 3,
+        tiff::ColorType::RGBA(_) => 4,
+        _ => return Err(TiffError::UnsupportedColorType(color_type)),
+    };
+
+    if channels != expected {
+        return Err(TiffError::ChannelCountMismatch {
+            expected,
+            actual: channels,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tiff::{DecodedImage, ImageDecoder, ImageEncoder, TiffCompression, TiffError};
+    use kornia_image::{Image, ImageSize};
+
+    #[test]
+    fn tiff_roundtrip_uncompressed() -> Result<(), TiffError> {
+        let image = Image::<u8, 1>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![128u8; 16],
+        )?;
+
+        let tiff_data = ImageEncoder::new().encode::<1>(&image)?;
+
+        let image_size = ImageDecoder::new().read_header(&tiff_data)?;
+        assert_eq!(image_size.width, 4);
+        assert_eq!(image_size.height, 4);
+
+        let image_back = ImageDecoder::new().decode::<1>(&tiff_data)?;
+        assert_eq!(image_back.as_slice(), image.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn tiff_roundtrip_lzw() -> Result<(), TiffError> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![10u8; 4 * 4 * 3],
+        )?;
+
+        let mut encoder = ImageEncoder::new();
+        encoder.set_compression(TiffCompression::Lzw);
+        let tiff_data = encoder.encode::<3>(&image)?;
+
+        let image_back = ImageDecoder::new().decode::<3>(&tiff_data)?;
+        assert_eq!(image_back.size().width, 4);
+        assert_eq!(image_back.size().height, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn tiff_roundtrip_packbits() -> Result<(), TiffError> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![10u8; 4 * 4 * 3],
+        )?;
+
+        let mut encoder = ImageEncoder::new();
+        encoder.set_compression(TiffCompression::Packbits);
+        let tiff_data = encoder.encode::<3>(&image)?;
+
+        let image_back = ImageDecoder::new().decode::<3>(&tiff_data)?;
+        assert_eq!(image_back.as_slice(), image.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn tiff_roundtrip_deflate() -> Result<(), TiffError> {
+        let image = Image::<u8, 3>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![10u8; 4 * 4 * 3],
+        )?;
+
+        let mut encoder = ImageEncoder::new();
+        encoder.set_compression(TiffCompression::Deflate);
+        let tiff_data = encoder.encode::<3>(&image)?;
+
+        let image_back = ImageDecoder::new().decode::<3>(&tiff_data)?;
+        assert_eq!(image_back.as_slice(), image.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn tiff_roundtrip_16bit() -> Result<(), TiffError> {
+        let image = Image::<u16, 1>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![4096u16; 16],
+        )?;
+
+        let tiff_data = ImageEncoder::new().encode16::<1>(&image)?;
+
+        let image_back = ImageDecoder::new().decode16::<1>(&tiff_data)?;
+        assert_eq!(image_back.as_slice(), image.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn tiff_decode_auto_matches_color_type() -> Result<(), TiffError> {
+        let image = Image::<u16, 3>::new(
+            ImageSize {
+                width: 4,
+                height: 4,
+            },
+            vec![256u16; 4 * 4 * 3],
+        )?;
+
+        let tiff_data = ImageEncoder::new().encode16::<3>(&image)?;
+
+        let decoded = ImageDecoder::new().decode_auto(&tiff_data)?;
+        let DecodedImage::Rgb16(rgb_image) = decoded else {
+            panic!("expected a 16-bit RGB image");
+        };
+        assert_eq!(rgb_image.as_slice(), image.as_slice());
+        Ok(())
+    }
+}